@@ -1,60 +1,348 @@
-use std::process::{Child, Command};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tauri::{
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WindowEvent,
+    menu::{Menu, MenuItem, PredefinedMenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
+    Manager, RunEvent, WindowEvent, Wry,
 };
 
-struct PythonProcess(Mutex<Option<Child>>);
+const DEFAULT_BACKEND_HOST: &str = "127.0.0.1";
+const DEFAULT_BACKEND_PORT: u16 = 5000;
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const RESTART_BASE_DELAY: Duration = Duration::from_millis(500);
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+const RESTART_STABILITY_WINDOW: Duration = Duration::from_secs(10);
+const MAX_CONSECUTIVE_RESTARTS: u32 = 8;
+const LOG_BUFFER_CAPACITY: usize = 500;
+const BACKEND_CONFIG_FILE: &str = "backend-config.json";
+
+/// Where and how to launch the Python backend, persisted in the app config dir so
+/// users aren't stuck with the hard-coded dev/release paths this used to ship with.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct BackendConfig {
+    interpreter: String,
+    script_path: String,
+    working_dir: String,
+    host: String,
+    port: u16,
+    env: HashMap<String, String>,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        #[cfg(debug_assertions)]
+        {
+            Self {
+                interpreter: "python3".to_string(),
+                script_path: "app.py".to_string(),
+                working_dir: "../..".to_string(),
+                host: DEFAULT_BACKEND_HOST.to_string(),
+                port: DEFAULT_BACKEND_PORT,
+                env: HashMap::new(),
+            }
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            Self {
+                interpreter: "./binaries/python-server".to_string(),
+                script_path: String::new(),
+                working_dir: ".".to_string(),
+                host: DEFAULT_BACKEND_HOST.to_string(),
+                port: DEFAULT_BACKEND_PORT,
+                env: HashMap::new(),
+            }
+        }
+    }
+}
+
+fn backend_config_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(BACKEND_CONFIG_FILE))
+}
+
+fn load_backend_config(app_handle: &tauri::AppHandle) -> BackendConfig {
+    backend_config_path(app_handle)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+struct PythonProcessState {
+    child: Option<Child>,
+    /// Bumped by `start_python_server`/`stop_python_server` on every (re)start or stop.
+    /// A running supervisor task compares this against the generation it was spawned
+    /// with and stands down as soon as they diverge, so at most one supervisor is ever
+    /// acting on a given child and a stop never leaves a stale task polling forever.
+    generation: u64,
+    restart_count: u32,
+    logs: VecDeque<LogLine>,
+    config: BackendConfig,
+}
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+struct LogLine {
+    stream: LogStream,
+    text: String,
+}
+
+/// Reads `reader` line by line, appending each line to the bounded log ring buffer and
+/// forwarding it to the webview so the frontend can show a live backend console.
+fn spawn_log_reader<R: Read + Send + 'static>(
+    app_handle: tauri::AppHandle,
+    reader: R,
+    stream: LogStream,
+) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let Ok(text) = line else {
+                break;
+            };
+            let log_line = LogLine { stream, text };
+
+            if let Ok(mut process) = app_handle.state::<PythonProcess>().0.lock() {
+                if process.logs.len() >= LOG_BUFFER_CAPACITY {
+                    process.logs.pop_front();
+                }
+                process.logs.push_back(log_line.clone());
+            }
+
+            let _ = app_handle.emit("python-server://log", log_line);
+        }
+    });
+}
+
+fn spawn_backend_child(app_handle: &tauri::AppHandle, config: &BackendConfig) -> Result<Child, String> {
+    let mut command = Command::new(&config.interpreter);
+    if !config.script_path.is_empty() {
+        command.arg(&config.script_path);
+    }
+    command.current_dir(&config.working_dir);
+    command.env("CHATBOT_PORT", config.port.to_string());
+    for (key, value) in &config.env {
+        command.env(key, value);
+    }
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start Python server: {}", e))?;
+
+    if let Some(stdout) = child.stdout.take() {
+        spawn_log_reader(app_handle.clone(), stdout, LogStream::Stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_log_reader(app_handle.clone(), stderr, LogStream::Stderr);
+    }
+
+    Ok(child)
+}
+
+/// Watches the child spawned by `start_python_server` and restarts it on an
+/// unexpected exit, backing off exponentially between consecutive crashes. Gives up
+/// and emits `python-server://failed` when the backend exits again after
+/// `MAX_CONSECUTIVE_RESTARTS` restart attempts (failed spawns included) without an
+/// intervening stability window. Stands down as soon as `generation` no longer
+/// matches `PythonProcessState::generation`, which `stop_python_server` bumps on every
+/// stop/restart — so a stop is detected immediately rather than by polling for a
+/// `child` that `stop_python_server` may already have cleared out from under us.
+fn spawn_supervisor(app_handle: tauri::AppHandle, generation: u64) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<PythonProcess>();
+        let mut restart_delay = RESTART_BASE_DELAY;
+        let mut last_restart = Instant::now();
+
+        loop {
+            async_io::Timer::after(SUPERVISOR_POLL_INTERVAL).await;
+
+            let is_current = state.0.lock().map(|p| p.generation == generation).unwrap_or(false);
+            if !is_current {
+                return;
+            }
+
+            let exited = {
+                let mut process = match state.0.lock() {
+                    Ok(guard) => guard,
+                    Err(_) => continue,
+                };
+                let exited = matches!(
+                    process.child.as_mut().map(|child| child.try_wait()),
+                    Some(Ok(Some(_)))
+                );
+                if exited {
+                    process.child = None;
+                }
+                exited
+            };
+
+            if !exited {
+                continue;
+            }
+
+            if last_restart.elapsed() >= RESTART_STABILITY_WINDOW {
+                restart_delay = RESTART_BASE_DELAY;
+                if let Ok(mut process) = state.0.lock() {
+                    process.restart_count = 0;
+                }
+            }
+
+            loop {
+                let restart_count = state.0.lock().map(|p| p.restart_count).unwrap_or(0);
+                if restart_count >= MAX_CONSECUTIVE_RESTARTS {
+                    update_tray_state(&app_handle, false);
+                    let _ = app_handle.emit("python-server://failed", ());
+                    return;
+                }
+
+                async_io::Timer::after(restart_delay).await;
+                restart_delay = (restart_delay * 2).min(RESTART_MAX_DELAY);
+
+                let is_current = state.0.lock().map(|p| p.generation == generation).unwrap_or(false);
+                if !is_current {
+                    return;
+                }
+
+                let config = match state.0.lock() {
+                    Ok(mut process) => {
+                        process.restart_count += 1;
+                        process.config.clone()
+                    }
+                    Err(_) => continue,
+                };
+
+                match spawn_backend_child(&app_handle, &config) {
+                    Ok(child) => {
+                        if let Ok(mut process) = state.0.lock() {
+                            process.child = Some(child);
+                        }
+                        last_restart = Instant::now();
+                        update_tray_state(&app_handle, true);
+                        let _ = app_handle.emit("python-server://restarted", ());
+                        break;
+                    }
+                    Err(_) => continue,
+                }
+            }
+        }
+    });
+}
+
+struct PythonProcess(Mutex<PythonProcessState>);
+
+/// Handles kept around so backend state changes can be reflected live in the tray
+/// (tooltip text, which menu items are enabled) without rebuilding the menu.
+struct TrayHandles {
+    tray: TrayIcon,
+    start_item: MenuItem<Wry>,
+    stop_item: MenuItem<Wry>,
+    restart_item: MenuItem<Wry>,
+}
+
+fn update_tray_state(app_handle: &tauri::AppHandle, running: bool) {
+    let Some(tray_handles) = app_handle.try_state::<TrayHandles>() else {
+        return;
+    };
+
+    let tooltip = if running {
+        "Chat Bot — server running"
+    } else {
+        "Chat Bot — server stopped"
+    };
+
+    let _ = tray_handles.tray.set_tooltip(Some(tooltip));
+    let _ = tray_handles.start_item.set_enabled(!running);
+    let _ = tray_handles.stop_item.set_enabled(running);
+    let _ = tray_handles.restart_item.set_enabled(running);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ServerStatus {
+    Starting,
+    Ready,
+    Dead,
+}
 
 #[tauri::command]
-fn start_python_server(state: tauri::State<PythonProcess>) -> Result<String, String> {
+fn start_python_server(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<PythonProcess>,
+) -> Result<String, String> {
     let mut process = state.0.lock().map_err(|e| e.to_string())?;
 
-    if process.is_some() {
+    if process.child.is_some() {
+        update_tray_state(&app_handle, true);
         return Ok("Python server already running".to_string());
     }
 
-    // In development, start from project root
-    #[cfg(debug_assertions)]
-    let child = Command::new("python3")
-        .arg("app.py")
-        .current_dir("../..")
-        .spawn()
-        .map_err(|e| format!("Failed to start Python server: {}", e))?;
+    let config = process.config.clone();
+    process.child = Some(spawn_backend_child(&app_handle, &config)?);
+    process.generation = process.generation.wrapping_add(1);
+    let generation = process.generation;
+    process.restart_count = 0;
+    drop(process);
 
-    // In production, use bundled binary
-    #[cfg(not(debug_assertions))]
-    let child = Command::new("./binaries/python-server")
-        .spawn()
-        .map_err(|e| format!("Failed to start Python server: {}", e))?;
+    spawn_supervisor(app_handle.clone(), generation);
+    update_tray_state(&app_handle, true);
 
-    *process = Some(child);
     Ok("Python server started".to_string())
 }
 
 #[tauri::command]
-fn stop_python_server(state: tauri::State<PythonProcess>) -> Result<String, String> {
+fn stop_python_server(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<PythonProcess>,
+) -> Result<String, String> {
     let mut process = state.0.lock().map_err(|e| e.to_string())?;
 
-    if let Some(ref mut child) = *process {
+    process.generation = process.generation.wrapping_add(1);
+
+    let result = if let Some(ref mut child) = process.child {
         child.kill().map_err(|e| format!("Failed to kill Python server: {}", e))?;
-        *process = None;
+        process.child = None;
         Ok("Python server stopped".to_string())
     } else {
         Ok("Python server not running".to_string())
-    }
+    };
+
+    drop(process);
+    update_tray_state(&app_handle, false);
+
+    result
+}
+
+#[tauri::command]
+fn restart_python_server(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let _ = stop_python_server(app_handle.clone(), app_handle.state::<PythonProcess>());
+    start_python_server(app_handle.clone(), app_handle.state::<PythonProcess>())
+        .map(|_| "Python server restarted".to_string())
 }
 
 #[tauri::command]
 fn check_python_server(state: tauri::State<PythonProcess>) -> Result<bool, String> {
     let mut process = state.0.lock().map_err(|e| e.to_string())?;
 
-    if let Some(ref mut child) = *process {
+    if let Some(ref mut child) = process.child {
         match child.try_wait() {
             Ok(Some(_)) => {
                 // Process has exited
-                *process = None;
+                process.child = None;
                 Ok(false)
             }
             Ok(None) => Ok(true), // Still running
@@ -65,16 +353,173 @@ fn check_python_server(state: tauri::State<PythonProcess>) -> Result<bool, Strin
     }
 }
 
+#[tauri::command]
+fn get_python_logs(state: tauri::State<PythonProcess>) -> Result<Vec<LogLine>, String> {
+    let process = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(process.logs.iter().cloned().collect())
+}
+
+#[tauri::command]
+fn get_backend_config(state: tauri::State<PythonProcess>) -> Result<BackendConfig, String> {
+    let process = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(process.config.clone())
+}
+
+#[tauri::command]
+fn set_backend_config(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<PythonProcess>,
+    config: BackendConfig,
+) -> Result<String, String> {
+    let path = backend_config_path(&app_handle)?;
+    let contents = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+
+    {
+        let mut process = state.0.lock().map_err(|e| e.to_string())?;
+        process.config = config;
+    }
+
+    restart_python_server(app_handle)
+}
+
+/// Sends a minimal HTTP GET to `host:port` and reports whether the response's status
+/// line is a 200. Used instead of a bare TCP connect because a socket can be accepting
+/// connections before the backend is actually ready to serve a request (e.g. Flask's
+/// listener comes up well before the app context finishes initializing).
+async fn probe_http_ready(host: &str, port: u16) -> bool {
+    use futures_lite::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let Ok(mut stream) = async_net::TcpStream::connect((host, port)).await else {
+        return false;
+    };
+
+    let request = format!("GET / HTTP/1.0\r\nHost: {}\r\n\r\n", host);
+    if stream.write_all(request.as_bytes()).await.is_err() {
+        return false;
+    }
+
+    let mut status_line = String::new();
+    let mut reader = BufReader::new(stream);
+    if reader.read_line(&mut status_line).await.is_err() {
+        return false;
+    }
+
+    status_line.contains(" 200 ")
+}
+
+/// Polls the backend's configured endpoint until it returns HTTP 200, the process is
+/// observed dead, or `timeout_ms` elapses, so the frontend can wait for a real
+/// "ready to serve a request" state instead of just "the OS says the child exists".
+#[tauri::command]
+async fn wait_for_python_ready(
+    state: tauri::State<'_, PythonProcess>,
+    timeout_ms: u64,
+) -> Result<ServerStatus, String> {
+    let (host, port) = {
+        let process = state.0.lock().map_err(|e| e.to_string())?;
+        if process.child.is_none() {
+            return Ok(ServerStatus::Dead);
+        }
+        (process.config.host.clone(), process.config.port)
+    };
+
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+    loop {
+        if probe_http_ready(&host, port).await {
+            return Ok(ServerStatus::Ready);
+        }
+
+        {
+            let mut process = state.0.lock().map_err(|e| e.to_string())?;
+            if let Some(ref mut child) = process.child {
+                if matches!(child.try_wait(), Ok(Some(_))) {
+                    process.child = None;
+                    return Ok(ServerStatus::Dead);
+                }
+            } else {
+                return Ok(ServerStatus::Dead);
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Ok(ServerStatus::Starting);
+        }
+
+        async_io::Timer::after(READY_POLL_INTERVAL).await;
+    }
+}
+
+/// Kills the backend child on a genuine app exit, so quitting from the tray or the OS
+/// never leaves an orphaned Python process behind.
+fn kill_backend(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<PythonProcess>();
+    if let Ok(mut process) = state.0.lock() {
+        process.generation = process.generation.wrapping_add(1);
+        if let Some(ref mut child) = process.child {
+            let _ = child.kill();
+            process.child = None;
+        }
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let app = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
-        .manage(PythonProcess(Mutex::new(None)))
+        .manage(PythonProcess(Mutex::new(PythonProcessState {
+            child: None,
+            generation: 0,
+            restart_count: 0,
+            logs: VecDeque::with_capacity(LOG_BUFFER_CAPACITY),
+            config: BackendConfig::default(),
+        })))
         .setup(|app| {
-            // Create system tray
-            let _tray = TrayIconBuilder::new()
+            // Create system tray with a full control-panel menu
+            let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
+            let start_item = MenuItem::with_id(app, "start", "Start Server", true, None::<&str>)?;
+            let stop_item = MenuItem::with_id(app, "stop", "Stop Server", false, None::<&str>)?;
+            let restart_item =
+                MenuItem::with_id(app, "restart", "Restart Server", false, None::<&str>)?;
+            let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+
+            let menu = Menu::with_items(
+                app,
+                &[
+                    &show_item,
+                    &PredefinedMenuItem::separator(app)?,
+                    &start_item,
+                    &stop_item,
+                    &restart_item,
+                    &PredefinedMenuItem::separator(app)?,
+                    &quit_item,
+                ],
+            )?;
+
+            let tray = TrayIconBuilder::new()
                 .tooltip("Chat Bot")
+                .menu(&menu)
+                .on_menu_event(|app, event| match event.id().as_ref() {
+                    "show" => {
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
+                    }
+                    "start" => {
+                        let _ = start_python_server(app.clone(), app.state::<PythonProcess>());
+                    }
+                    "stop" => {
+                        let _ = stop_python_server(app.clone(), app.state::<PythonProcess>());
+                    }
+                    "restart" => {
+                        let _ = restart_python_server(app.clone());
+                    }
+                    "quit" => app.exit(0),
+                    _ => {}
+                })
                 .on_tray_icon_event(|tray, event| {
                     if let TrayIconEvent::Click {
                         button: MouseButton::Left,
@@ -91,11 +536,26 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            app.manage(TrayHandles {
+                tray,
+                start_item,
+                stop_item,
+                restart_item,
+            });
+
+            // Load any persisted backend config before the first start
+            let config = load_backend_config(&app.handle().clone());
+            {
+                let state = app.state::<PythonProcess>();
+                let mut process = state.0.lock().map_err(|e| e.to_string())?;
+                process.config = config;
+            }
+
             // Auto-start Python server in development
             #[cfg(debug_assertions)]
             {
                 let state = app.state::<PythonProcess>();
-                let _ = start_python_server(state);
+                let _ = start_python_server(app.handle().clone(), state);
             }
 
             Ok(())
@@ -110,8 +570,18 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             start_python_server,
             stop_python_server,
-            check_python_server
+            restart_python_server,
+            check_python_server,
+            wait_for_python_ready,
+            get_python_logs,
+            get_backend_config,
+            set_backend_config
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application");
+
+    app.run(|app_handle, event| match event {
+        RunEvent::ExitRequested { .. } | RunEvent::Exit => kill_backend(app_handle),
+        _ => {}
+    });
 }